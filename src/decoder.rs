@@ -1,22 +1,92 @@
+use core::marker::PhantomData;
+
 use super::*;
 
-/// SLIP decode context
-pub struct Decoder {
-    header_found: bool,
+/// SLIP decode context, generic over the [`Framing`] rules used to delimit
+/// frames. Defaults to [`Rfc1055`] (classic RFC 1055 SLIP); construct a
+/// different framing with [`Decoder::new_with`].
+pub struct Decoder<F: Framing = Rfc1055> {
+    header_matched: usize,
     esc_seq: [u8; 4],
     esc_seq_len: usize,
+    resync_enabled: bool,
+    desynced: bool,
+    resyncs: u32,
+    /// Running CRC16 over the payload, if CRC checking is enabled.
+    crc: Option<u16>,
+    /// Last two decoded payload bytes, held back since they might turn out
+    /// to be the trailing CRC rather than payload.
+    crc_buf: [u8; 2],
+    crc_buf_len: usize,
+    /// Number of `Framing::END` bytes matched in a row so far.
+    end_matched: usize,
+    /// Raw on-the-wire bytes consumed for the current frame so far, tracked
+    /// only to compute `Framing::PAD_TO` padding.
+    frame_len: usize,
+    /// Trailing padding bytes still to be skipped before the next frame.
+    pad_remaining: usize,
+    _framing: PhantomData<F>,
 }
 
-impl Decoder {
+impl Decoder<Rfc1055> {
     /// Create a new context for SLIP decoding
     pub fn new() -> Self {
+        Self::new_with()
+    }
+}
+
+impl<F: Framing> Decoder<F> {
+    /// Create a new context for decoding framed by `F` instead of
+    /// [`Rfc1055`].
+    pub fn new_with() -> Self {
         Decoder {
-            header_found: false,
+            header_matched: 0,
             esc_seq: [0; 4],
             esc_seq_len: 0,
+            resync_enabled: false,
+            desynced: false,
+            resyncs: 0,
+            crc: None,
+            crc_buf: [0; 2],
+            crc_buf_len: 0,
+            end_matched: 0,
+            frame_len: 0,
+            pad_remaining: 0,
+            _framing: PhantomData,
+        }
+    }
+
+    /// Create a context for decoding that recovers from malformed escape
+    /// sequences instead of returning a fatal error.
+    ///
+    /// When an `ESC` is followed by anything other than `ESC_END`/`ESC_ESC`,
+    /// the in-progress frame is discarded and incoming bytes are silently
+    /// dropped until the next `END` delimiter, at which point normal framing
+    /// resumes. Use `resync_count` to find out how many times this happened.
+    pub fn with_resync() -> Self {
+        Decoder {
+            resync_enabled: true,
+            ..Self::new_with()
+        }
+    }
+
+    /// Create a context for decoding that expects a trailing CRC16 checksum
+    /// (over the raw, pre-escape payload) immediately before each `END`,
+    /// verifying it and returning `Error::BadCrc` on mismatch.
+    pub fn with_crc16() -> Self {
+        Decoder {
+            crc: Some(CRC16_INIT),
+            ..Self::new_with()
         }
     }
 
+    /// Number of frames this decoder has discarded and resynchronized past
+    /// after a malformed escape sequence. Only increments when constructed
+    /// via `with_resync`.
+    pub fn resync_count(&self) -> u32 {
+        self.resyncs
+    }
+
     /// SLIP decode the input slice into the output slice.
     ///
     /// This returns the number of bytes processed, an output slice and an indication of
@@ -26,27 +96,49 @@ impl Decoder {
     {
         let input_len = input.len();
         let mut stream = input;
-        if !self.header_found {
-            stream = self.decode_header(stream)?;
+
+        if self.pad_remaining > 0 {
+            let skip = self.pad_remaining.min(stream.len());
+            self.pad_remaining -= skip;
+            stream = &stream[skip..];
         }
-        let res = self.decode_stream(stream, output)?;
 
-        Ok((input_len - res.0.len(), res.1, res.2))
-    }
+        if self.header_matched < F::START.len() {
+            stream = match self.decode_header(stream)? {
+                Some(rest) => rest,
+                None => return Ok((input_len, &output[..0], false)),
+            };
+        }
+        let res = self.decode_stream(stream, output)?;
+        let mut remaining = res.0;
 
-    /// Either process the header successfully or return an error
-    fn decode_header<'a>(&mut self, input: &'a [u8]) -> Result<&'a [u8]> {
-        if input.len() < 1 {
-            // TODO: decode partial headers! For now, just error out...
-            return Err(Error::BadHeaderDecode);
+        if self.pad_remaining > 0 {
+            let skip = self.pad_remaining.min(remaining.len());
+            self.pad_remaining -= skip;
+            remaining = &remaining[skip..];
         }
 
-        if input[0] != END {
-            return Err(Error::BadHeaderDecode);
+        Ok((input_len - remaining.len(), res.1, res.2))
+    }
+
+    /// Match `input` against whatever's left of `Framing::START`, returning
+    /// the unmatched remainder once complete, `None` if `input` ran out
+    /// first (so the caller can retry once more bytes arrive), or an error.
+    fn decode_header<'a>(&mut self, input: &'a [u8]) -> Result<Option<&'a [u8]>> {
+        let mut pos = 0;
+        while self.header_matched < F::START.len() {
+            if pos == input.len() {
+                return Ok(None);
+            }
+            if input[pos] != F::START[self.header_matched] {
+                return Err(Error::BadHeaderDecode);
+            }
+            self.header_matched += 1;
+            self.frame_len += 1;
+            pos += 1;
         }
-        self.header_found = true;
 
-        Ok(&input[1..])
+        Ok(Some(&input[pos..]))
     }
 
     /// Core stream processing
@@ -62,40 +154,160 @@ impl Decoder {
                 break;
             }
 
+            if self.desynced {
+                self.frame_len += 1;
+                if input[in_byte] == F::END[self.end_matched] {
+                    self.end_matched += 1;
+                    in_byte += 1;
+                    if self.end_matched == F::END.len() {
+                        self.desynced = false;
+                        self.reset_frame_state();
+                        // When START and END differ, the next frame's START
+                        // hasn't been matched yet: stop here so the caller's
+                        // next `decode` call re-runs header matching instead
+                        // of this call treating those bytes as payload.
+                        if F::START != F::END {
+                            break;
+                        }
+                    }
+                    continue;
+                } else {
+                    self.end_matched = usize::from(input[in_byte] == F::END[0]);
+                    in_byte += 1;
+                    continue;
+                }
+            }
+
             if self.esc_seq_len > 0 {
-                match input[in_byte] {
-                    ESC_END => {
-                        output[out_byte] = END
+                match F::unescape(input[in_byte]) {
+                    Some(byte) => {
+                        self.push_decoded(byte, output, &mut out_byte);
                     }
-                    ESC_ESC => {
-                        output[out_byte] = ESC
+                    None => {
+                        if !self.resync_enabled {
+                            return Err(Error::BadEscapeSequenceDecode);
+                        }
+                        self.esc_sequence_empty();
+                        self.desynced = true;
+                        self.resyncs += 1;
+                        out_byte = 0;
+                        self.crc_buf_len = 0;
+                        if self.crc.is_some() {
+                            self.crc = Some(CRC16_INIT);
+                        }
+                        self.frame_len += 1;
+                        in_byte += 1;
+                        continue;
                     }
-                    _ => return Err(Error::BadEscapeSequenceDecode),
                 }
-                out_byte += 1;
                 self.esc_sequence_empty();
             } else {
-                match input[in_byte] {
-                    ESC => {
-                        self.esc_sequence_push(ESC);
-                    }
-                    END => {
+                let byte = input[in_byte];
+                if self.end_matched == 0 && byte == F::ESC {
+                    self.esc_sequence_push(F::ESC);
+                } else if byte == F::END[self.end_matched] {
+                    self.end_matched += 1;
+                    self.frame_len += 1;
+                    if self.end_matched == F::END.len() {
                         in_byte += 1;
                         end = true;
                         break;
                     }
-                    _ => {
-                        output[out_byte] = input[in_byte];
-                        out_byte += 1;
-                    }
+                    in_byte += 1;
+                    continue;
+                } else if self.end_matched > 0 {
+                    return Err(Error::BadEndSequence);
+                } else {
+                    self.push_decoded(byte, output, &mut out_byte);
                 }
             }
+            self.frame_len += 1;
             in_byte += 1;
         }
 
+        if end {
+            self.finish_frame()?;
+        }
+
         Ok((&input[in_byte..], &output[..out_byte], end))
     }
 
+    /// Emit a decoded payload byte, or (when CRC checking is enabled) hold
+    /// it back in `crc_buf` since it might turn out to be one of the two
+    /// trailing CRC bytes rather than payload.
+    fn push_decoded(&mut self, byte: u8, output: &mut [u8], out_byte: &mut usize) {
+        if self.crc.is_none() {
+            output[*out_byte] = byte;
+            *out_byte += 1;
+            return;
+        }
+
+        if self.crc_buf_len == 2 {
+            let flushed = self.crc_buf[0];
+            output[*out_byte] = flushed;
+            *out_byte += 1;
+            if let Some(crc) = &mut self.crc {
+                *crc = crc16_update(*crc, flushed);
+            }
+            self.crc_buf[0] = self.crc_buf[1];
+            self.crc_buf[1] = byte;
+        } else {
+            self.crc_buf[self.crc_buf_len] = byte;
+            self.crc_buf_len += 1;
+        }
+    }
+
+    /// Verify the trailing CRC16 (if enabled), queue up `Framing::PAD_TO`
+    /// padding to skip, and reset per-frame state for the next frame.
+    fn finish_frame(&mut self) -> Result<()> {
+        let crc_result = match self.crc {
+            Some(crc) if self.crc_buf_len != 2 => Err(Error::BadCrc {
+                expected: 0,
+                actual: crc,
+            }),
+            Some(crc) => {
+                let expected = ((self.crc_buf[0] as u16) << 8) | self.crc_buf[1] as u16;
+                if expected == crc {
+                    Ok(())
+                } else {
+                    Err(Error::BadCrc {
+                        expected,
+                        actual: crc,
+                    })
+                }
+            }
+            None => Ok(()),
+        };
+
+        self.reset_frame_state();
+
+        crc_result
+    }
+
+    /// Reset the per-frame bookkeeping (CRC accumulator, `Framing::PAD_TO`
+    /// padding, `frame_len`/`end_matched`, and `header_matched` when
+    /// `Framing::START` and `Framing::END` differ) for the next frame.
+    ///
+    /// Shared by [`finish_frame`](Decoder::finish_frame) and resync
+    /// completion in [`decode_stream`](Decoder::decode_stream), since a
+    /// frame discarded via resync needs the exact same reset as one that
+    /// ended normally, minus the CRC verification.
+    fn reset_frame_state(&mut self) {
+        self.crc_buf_len = 0;
+        if self.crc.is_some() {
+            self.crc = Some(CRC16_INIT);
+        }
+
+        if F::PAD_TO > 0 {
+            self.pad_remaining = (F::PAD_TO - (self.frame_len % F::PAD_TO)) % F::PAD_TO;
+        }
+        self.frame_len = 0;
+        self.end_matched = 0;
+        if F::START != F::END {
+            self.header_matched = 0;
+        }
+    }
+
     /// Push a byte onto the escape sequence
     fn esc_sequence_push(&mut self, byte: u8) {
         self.esc_seq[self.esc_seq_len] = byte;
@@ -124,6 +336,32 @@ mod tests {
         assert_eq!(true, res.2);
     }
 
+    #[test]
+    fn decode_with_empty_input_before_header_is_found() {
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::new();
+        let res = slip.decode(&[], &mut output).unwrap();
+        assert_eq!(0, res.0);
+        assert_eq!(&[0; 0], res.1);
+        assert_eq!(false, res.2);
+    }
+
+    #[test]
+    fn header_found_in_later_decode_call() {
+        const DATA: [u8; 3] = [0x01, 0x02, 0x03];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::new();
+        let res = slip.decode(&[], &mut output).unwrap();
+        assert_eq!((0, &[][..], false), res);
+
+        let res = slip.decode(&[0xc0, 0x01, 0x02, 0x03, 0xc0], &mut output).unwrap();
+        assert_eq!(5, res.0);
+        assert_eq!(&DATA, res.1);
+        assert_eq!(true, res.2);
+    }
+
     #[test]
     fn simple_decode() {
         const INPUT: [u8; 7] = [0xc0, 0x01, 0x02, 0x03, 0x04, 0x05, 0xc0];
@@ -191,4 +429,196 @@ mod tests {
         }
         assert_eq!(10, offset);
     }
+
+    #[test]
+    fn without_resync_bad_escape_sequence_is_fatal() {
+        const INPUT: [u8; 4] = [0xc0, 0x01, 0xdb, 0x02];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::new();
+        assert!(slip.decode(&INPUT, &mut output).is_err());
+    }
+
+    #[test]
+    fn with_crc16_strips_and_verifies_checksum() {
+        const INPUT: [u8; 7] = [0xc0, 0x01, 0x02, 0x03, 0xad, 0xad, 0xc0];
+        const DATA: [u8; 3] = [0x01, 0x02, 0x03];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip: Decoder = Decoder::with_crc16();
+        let res = slip.decode(&INPUT, &mut output).unwrap();
+        assert_eq!(INPUT.len(), res.0);
+        assert_eq!(&DATA, res.1);
+        assert_eq!(true, res.2);
+    }
+
+    #[test]
+    fn with_crc16_rejects_mismatched_checksum() {
+        const INPUT: [u8; 7] = [0xc0, 0x01, 0x02, 0x03, 0xad, 0xae, 0xc0];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip: Decoder = Decoder::with_crc16();
+        let err = slip.decode(&INPUT, &mut output).unwrap_err();
+        assert!(matches!(err, Error::BadCrc { expected: 0xadae, actual: 0xadad }));
+    }
+
+    #[test]
+    fn with_resync_recovers_at_next_end() {
+        // A bad escape sequence (0xdb not followed by 0xdc/0xdd), discarded
+        // up to the next END, followed by a clean frame.
+        const INPUT: [u8; 9] = [
+            0xc0, 0x01, 0xdb, 0x02, // garbled, discarded at the next END
+            0xc0, 0x09, 0x0a, 0x0b, 0xc0, // clean frame
+        ];
+        const DATA: [u8; 3] = [0x09, 0x0a, 0x0b];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip: Decoder = Decoder::with_resync();
+        let res = slip.decode(&INPUT, &mut output).unwrap();
+        assert_eq!(INPUT.len(), res.0);
+        assert_eq!(&DATA, res.1);
+        assert_eq!(true, res.2);
+        assert_eq!(1, slip.resync_count());
+    }
+
+    #[test]
+    fn with_resync_re_matches_start_when_distinct_from_end() {
+        // A garbled frame that triggers resync, followed by a clean frame,
+        // under a Framing where START != END. The clean frame's START must
+        // be re-matched as a header rather than decoded as payload.
+        use crate::test_support::AsymmetricFraming;
+
+        const INPUT: [u8; 11] = [
+            0xaa, 0xbb, 0x01, 0xee, 0xff, // garbled, discarded at the next END
+            0xcc, // resync completes here
+            0xaa, 0xbb, 0x05, 0x06, 0xcc, // clean frame
+        ];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::<AsymmetricFraming>::with_resync();
+
+        // Resync completes mid-call; the clean frame's header hasn't been
+        // matched yet, so this call reports no end of packet.
+        let res = slip.decode(&INPUT, &mut output).unwrap();
+        assert_eq!(6, res.0);
+        assert_eq!(&[0; 0], res.1);
+        assert_eq!(false, res.2);
+        assert_eq!(1, slip.resync_count());
+
+        let res = slip.decode(&INPUT[6..], &mut output).unwrap();
+        assert_eq!(5, res.0);
+        assert_eq!(&[0x05, 0x06], res.1);
+        assert_eq!(true, res.2);
+    }
+
+    use crate::test_support::TestFraming;
+
+    #[test]
+    fn decode_with_custom_framing_skips_padding() {
+        // START(2) + payload(2) + END(1) = 5 bytes, padded to 8.
+        const INPUT: [u8; 8] = [0xaa, 0xbb, 0x01, 0x02, 0xcc, 0, 0, 0];
+        const DATA: [u8; 2] = [0x01, 0x02];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::<TestFraming>::new_with();
+        let res = slip.decode(&INPUT, &mut output).unwrap();
+        assert_eq!(INPUT.len(), res.0);
+        assert_eq!(&DATA, res.1);
+        assert_eq!(true, res.2);
+    }
+
+    #[test]
+    fn decode_with_custom_framing_requires_fresh_start_each_frame() {
+        const INPUT: [u8; 16] = [
+            0xaa, 0xbb, 0x01, 0x02, 0xcc, 0, 0, 0, // frame 1, padded to 8
+            0xaa, 0xbb, 0x05, 0x06, 0xcc, 0, 0, 0, // frame 2, padded to 8
+        ];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::<TestFraming>::new_with();
+        let res = slip.decode(&INPUT, &mut output).unwrap();
+        assert_eq!(8, res.0);
+        assert_eq!(&[0x01, 0x02], res.1);
+        assert_eq!(true, res.2);
+
+        let res = slip.decode(&INPUT[8..], &mut output).unwrap();
+        assert_eq!(8, res.0);
+        assert_eq!(&[0x05, 0x06], res.1);
+        assert_eq!(true, res.2);
+    }
+
+    /// A `Framing` whose `END` is itself two bytes, so matching it exercises
+    /// `end_matched`'s partial-match bookkeeping.
+    struct MultiByteEndFraming;
+
+    impl Framing for MultiByteEndFraming {
+        const ESC: u8 = 0xee;
+        const START: &'static [u8] = &[0xcc, 0xdd];
+        const END: &'static [u8] = &[0xcc, 0xdd];
+
+        fn escape(byte: u8) -> Option<u8> {
+            match byte {
+                0xcc => Some(0x01),
+                0xdd => Some(0x02),
+                0xee => Some(0x03),
+                _ => None,
+            }
+        }
+
+        fn unescape(byte: u8) -> Option<u8> {
+            match byte {
+                0x01 => Some(0xcc),
+                0x02 => Some(0xdd),
+                0x03 => Some(0xee),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn decode_with_multi_byte_end_sequence() {
+        const INPUT: [u8; 6] = [0xcc, 0xdd, 0x01, 0x02, 0xcc, 0xdd];
+        const DATA: [u8; 2] = [0x01, 0x02];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::<MultiByteEndFraming>::new_with();
+        let res = slip.decode(&INPUT, &mut output).unwrap();
+        assert_eq!(INPUT.len(), res.0);
+        assert_eq!(&DATA, res.1);
+        assert_eq!(true, res.2);
+    }
+
+    #[test]
+    fn decode_with_multi_byte_end_mismatch_is_bad_end_sequence() {
+        // 0xcc starts a match against END, but the following byte isn't the
+        // rest of END, so the partial match must be reported as an error
+        // rather than silently treated as payload.
+        const INPUT: [u8; 5] = [0xcc, 0xdd, 0x01, 0xcc, 0x01];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::<MultiByteEndFraming>::new_with();
+        let err = slip.decode(&INPUT, &mut output).unwrap_err();
+        assert!(matches!(err, Error::BadEndSequence));
+    }
+
+    #[test]
+    fn with_resync_recovers_at_multi_byte_end() {
+        // A bad escape sequence is discarded up to the next full END, not
+        // just its first byte, so a lone 0xcc mid-discard (not followed by
+        // 0xdd) must not end the resync early.
+        const INPUT: [u8; 11] = [
+            0xcc, 0xdd, 0x01, 0xee, 0xff, // garbled, discarded at the next END
+            0xcc, 0x01, // 0xcc appears mid-discard but isn't followed by 0xdd
+            0xcc, 0xdd, // real END, resync completes here
+            0xcc, 0xdd, // clean empty frame
+        ];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Decoder::<MultiByteEndFraming>::with_resync();
+        let res = slip.decode(&INPUT, &mut output).unwrap();
+        assert_eq!(INPUT.len(), res.0);
+        assert_eq!(&[0; 0], res.1);
+        assert_eq!(true, res.2);
+        assert_eq!(1, slip.resync_count());
+    }
 }
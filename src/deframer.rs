@@ -0,0 +1,173 @@
+use alloc::vec::Vec;
+
+use crate::{Decoder, Error, Result};
+
+/// Default maximum decoded frame size, used when a `Deframer` is created
+/// with `new` instead of `with_max_frame_len`.
+const DEFAULT_MAX_FRAME_LEN: usize = 64 * 1024;
+
+/// Buffering SLIP deframer that accumulates arbitrary-sized reads and emits
+/// complete packets.
+///
+/// Unlike [`Decoder`], which requires the caller to track partial frames and
+/// output offsets across reads, `Deframer` owns its own growable buffer: feed
+/// it raw bytes as they arrive with [`push`](Deframer::push), then drain
+/// completed frames with [`next_frame`](Deframer::next_frame).
+pub struct Deframer {
+    decoder: Decoder,
+    raw: Vec<u8>,
+    raw_pos: usize,
+    frame: Vec<u8>,
+    frame_taken: bool,
+    max_frame_len: usize,
+    /// Set once the frame currently being decoded is found to exceed
+    /// `max_frame_len`, so its remaining bytes are silently discarded
+    /// instead of raising `Error::OversizedPacket` again for every
+    /// subsequent `next_frame` call that frame's tail happens to span.
+    discarding_oversized: bool,
+}
+
+impl Deframer {
+    /// Create a deframer with the default maximum frame size (64 KiB).
+    pub fn new() -> Self {
+        Self::with_max_frame_len(DEFAULT_MAX_FRAME_LEN)
+    }
+
+    /// Create a deframer that errors with `Error::OversizedPacket` instead of
+    /// growing its buffer past `max_frame_len` decoded bytes for a single
+    /// frame. This bounds memory growth when a delimiter never arrives on an
+    /// untrusted link.
+    pub fn with_max_frame_len(max_frame_len: usize) -> Self {
+        Deframer {
+            decoder: Decoder::new(),
+            raw: Vec::new(),
+            raw_pos: 0,
+            frame: Vec::new(),
+            frame_taken: false,
+            max_frame_len,
+            discarding_oversized: false,
+        }
+    }
+
+    /// Feed newly-read bytes into the deframer.
+    pub fn push(&mut self, input: &[u8]) {
+        self.raw.extend_from_slice(input);
+    }
+
+    /// Pop the next fully-decoded frame, if one is complete.
+    ///
+    /// Returns `Ok(None)` if the buffered input doesn't yet contain a
+    /// complete frame; call `push` with more data and try again. Returns
+    /// `Err(Error::OversizedPacket)` exactly once per frame that exceeds
+    /// `max_frame_len`, no matter how many `push`/`next_frame` calls its
+    /// bytes are spread across; the rest of that frame is then silently
+    /// discarded until the next one starts.
+    pub fn next_frame(&mut self) -> Result<Option<&[u8]>> {
+        if self.frame_taken {
+            self.frame.clear();
+            self.frame_taken = false;
+        }
+
+        let mut scratch = [0u8; 256];
+        loop {
+            if self.raw_pos == self.raw.len() {
+                self.compact();
+                return Ok(None);
+            }
+
+            let (consumed, decoded, end_of_packet) =
+                self.decoder.decode(&self.raw[self.raw_pos..], &mut scratch)?;
+            self.raw_pos += consumed;
+
+            if self.discarding_oversized {
+                if end_of_packet {
+                    self.discarding_oversized = false;
+                }
+                continue;
+            }
+
+            if self.frame.len() + decoded.len() > self.max_frame_len {
+                self.frame.clear();
+                self.discarding_oversized = !end_of_packet;
+                return Err(Error::OversizedPacket);
+            }
+            self.frame.extend_from_slice(decoded);
+
+            if end_of_packet {
+                self.compact();
+                self.frame_taken = true;
+                return Ok(Some(&self.frame));
+            }
+        }
+    }
+
+    /// Drop already-consumed bytes from the front of the raw buffer so it
+    /// doesn't grow unbounded across many frames.
+    fn compact(&mut self) {
+        if self.raw_pos > 0 {
+            self.raw.drain(..self.raw_pos);
+            self.raw_pos = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_across_pushes() {
+        let mut deframer = Deframer::new();
+        deframer.push(&[0xc0, 0x01, 0x02]);
+        assert!(deframer.next_frame().unwrap().is_none());
+
+        deframer.push(&[0x03, 0xc0]);
+        assert_eq!(&[0x01, 0x02, 0x03], deframer.next_frame().unwrap().unwrap());
+    }
+
+    #[test]
+    fn emits_multiple_frames_from_one_push() {
+        let mut deframer = Deframer::new();
+        // The END between the two frames is shared: it closes the first and
+        // opens the second, matching standard SLIP framing.
+        deframer.push(&[0xc0, 0x01, 0xc0, 0x02, 0xc0]);
+
+        assert_eq!(&[0x01], deframer.next_frame().unwrap().unwrap());
+        assert_eq!(&[0x02], deframer.next_frame().unwrap().unwrap());
+        assert_eq!(None, deframer.next_frame().unwrap());
+    }
+
+    #[test]
+    fn oversized_frame_errors() {
+        let mut deframer = Deframer::with_max_frame_len(2);
+        deframer.push(&[0xc0, 0x01, 0x02, 0x03, 0xc0]);
+
+        assert!(matches!(
+            deframer.next_frame(),
+            Err(Error::OversizedPacket)
+        ));
+    }
+
+    #[test]
+    fn oversized_frame_across_multiple_pushes_yields_one_error() {
+        let mut deframer = Deframer::with_max_frame_len(2);
+        deframer.push(&[0xc0, 0x01, 0x02, 0x03]);
+
+        assert!(matches!(
+            deframer.next_frame(),
+            Err(Error::OversizedPacket)
+        ));
+
+        // The rest of the same oversized frame, and the next_frame calls
+        // that observe it, must not raise Error::OversizedPacket again.
+        deframer.push(&[0x04, 0x05]);
+        assert_eq!(None, deframer.next_frame().unwrap());
+
+        deframer.push(&[0xc0]);
+        assert_eq!(None, deframer.next_frame().unwrap());
+
+        // A clean frame afterwards decodes normally.
+        deframer.push(&[0x06, 0xc0]);
+        assert_eq!(&[0x06], deframer.next_frame().unwrap().unwrap());
+    }
+}
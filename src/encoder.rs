@@ -1,10 +1,19 @@
+use core::marker::PhantomData;
+
 use super::*;
 
-/// SLIP encoder context
-#[derive(Clone)]
-pub struct Encoder {
+/// SLIP encoder context, generic over the [`Framing`] rules used to delimit
+/// frames. Defaults to [`Rfc1055`] (classic RFC 1055 SLIP); construct a
+/// different framing with [`Encoder::new_with`].
+pub struct Encoder<F: Framing = Rfc1055> {
     /// Just keep track of whether we have encoded the header yet
     header_written: bool,
+    /// Running CRC16 over the payload, if CRC checking is enabled.
+    crc: Option<u16>,
+    /// Raw on-the-wire bytes written for the current frame so far, tracked
+    /// only to compute `Framing::PAD_TO` padding.
+    frame_len: usize,
+    _framing: PhantomData<F>,
 }
 
 /// The return type of `encode` that holds the bytes read and byte written after
@@ -16,54 +25,76 @@ pub struct EncodeTotals {
     pub written: usize,
 }
 
-impl Encoder {
+impl Encoder<Rfc1055> {
     /// Create a new context for SLIP encoding
     pub fn new() -> Self {
+        Self::new_with()
+    }
+}
+
+impl<F: Framing> Encoder<F> {
+    /// Create a new context for encoding framed by `F` instead of
+    /// [`Rfc1055`].
+    pub fn new_with() -> Self {
         Encoder {
             header_written: false,
+            crc: None,
+            frame_len: 0,
+            _framing: PhantomData,
+        }
+    }
+
+    /// Create a context for encoding that appends a CRC16 checksum (over the
+    /// raw, pre-escape payload) before the trailing `Framing::END`.
+    pub fn with_crc16() -> Self {
+        Encoder {
+            crc: Some(CRC16_INIT),
+            ..Self::new_with()
         }
     }
 
-    /// Encode a buffer into a SLIP stream and returns the number of input bytes read
+    /// Create a context that picks up where a previous frame's
+    /// [`finish`](Encoder::finish) left off, without writing another leading
+    /// `Framing::START`.
+    ///
+    /// Used by [`SlipWriter`](crate::SlipWriter) to chain packets, since the
+    /// trailing `END` just written already serves as the next frame's
+    /// `START` under RFC 1055's shared-delimiter framing.
+    #[cfg(feature = "std")]
+    pub(crate) fn continuation() -> Self {
+        Encoder {
+            header_written: true,
+            ..Self::new_with()
+        }
+    }
+
+    /// Encode a buffer into a framed stream and returns the number of input bytes read
     /// and output bytes written.
     pub fn encode(&mut self, input: &[u8], output: &mut [u8]) -> Result<EncodeTotals> {
         let mut out_byte = 0;
         if !self.header_written {
-            if output.len() < 1 {
+            if output.len() < F::START.len() {
                 return Err(Error::NoOutputSpaceForHeader);
             }
 
-            output[out_byte] = END;
-            out_byte = 1;
+            output[..F::START.len()].copy_from_slice(F::START);
+            out_byte = F::START.len();
+            self.frame_len += out_byte;
             self.header_written = true;
         }
 
         let mut in_byte = 0;
         while in_byte < input.len() {
-            match input[in_byte] {
-                ESC => {
-                    if (output.len() - out_byte) < 2 {
-                        break;
-                    }
-                    output[out_byte] = ESC;
-                    output[out_byte + 1] = ESC_ESC;
-                    out_byte += 2;
-                }
-                END => {
-                    if (output.len() - out_byte) < 2 {
-                        break;
-                    }
-                    output[out_byte] = ESC;
-                    output[out_byte + 1] = ESC_END;
-                    out_byte += 2;
-                }
-                _ => {
-                    if (output.len() - out_byte) < 1 {
-                        break;
-                    }
-                    output[out_byte] = input[in_byte];
-                    out_byte += 1;
-                }
+            let byte = input[in_byte];
+            let len = escaped_len::<F>(byte);
+            if (output.len() - out_byte) < len {
+                break;
+            }
+            write_escaped::<F>(byte, &mut output[out_byte..out_byte + len]);
+            out_byte += len;
+            self.frame_len += len;
+            if let Some(crc) = &mut self.crc {
+                *crc = crc16_update(*crc, byte);
             }
             in_byte += 1;
         }
@@ -76,18 +107,80 @@ impl Encoder {
 
     /// Finish encoding the current packet and return the number of output bytes written.
     pub fn finish(self, output: &mut [u8]) -> Result<EncodeTotals> {
-        if output.len() < 1 {
+        let mut out_byte = 0;
+        let mut frame_len = self.frame_len;
+
+        if let Some(crc) = self.crc {
+            for &byte in &[(crc >> 8) as u8, crc as u8] {
+                let len = escaped_len::<F>(byte);
+                if (output.len() - out_byte) < len {
+                    return Err(Error::NoOutputSpaceForEndByte);
+                }
+                write_escaped::<F>(byte, &mut output[out_byte..out_byte + len]);
+                out_byte += len;
+                frame_len += len;
+            }
+        }
+
+        if output.len() - out_byte < F::END.len() {
             return Err(Error::NoOutputSpaceForEndByte);
         }
-        output[0] = END;
+        output[out_byte..out_byte + F::END.len()].copy_from_slice(F::END);
+        out_byte += F::END.len();
+        frame_len += F::END.len();
+
+        if F::PAD_TO > 0 {
+            let pad = (F::PAD_TO - (frame_len % F::PAD_TO)) % F::PAD_TO;
+            if output.len() - out_byte < pad {
+                return Err(Error::NoOutputSpaceForEndByte);
+            }
+            for byte in &mut output[out_byte..out_byte + pad] {
+                *byte = 0;
+            }
+            out_byte += pad;
+        }
 
         Ok(EncodeTotals {
             read: 0,
-            written: 1,
+            written: out_byte,
         })
     }
 }
 
+impl<F: Framing> Clone for Encoder<F> {
+    fn clone(&self) -> Self {
+        Encoder {
+            header_written: self.header_written,
+            crc: self.crc,
+            frame_len: self.frame_len,
+            _framing: PhantomData,
+        }
+    }
+}
+
+/// Number of output bytes needed to encode `byte` under framing `F`,
+/// escaping it if it collides with a delimiter.
+fn escaped_len<F: Framing>(byte: u8) -> usize {
+    match F::escape(byte) {
+        Some(_) => 2,
+        None => 1,
+    }
+}
+
+/// Write `byte` to the front of `output`, escaping it under framing `F` if
+/// necessary.
+fn write_escaped<F: Framing>(byte: u8, output: &mut [u8]) {
+    match F::escape(byte) {
+        Some(escaped) => {
+            output[0] = F::ESC;
+            output[1] = escaped;
+        }
+        None => {
+            output[0] = byte;
+        }
+    }
+}
+
 impl core::ops::AddAssign for EncodeTotals {
     fn add_assign(&mut self, other: EncodeTotals) {
         *self = EncodeTotals {
@@ -145,6 +238,34 @@ mod tests {
         assert_eq!(&EXPECTED, &output[..totals.written]);
     }
 
+    #[test]
+    fn encode_with_crc16_appends_checksum() {
+        const INPUT: [u8; 3] = [0x01, 0x02, 0x03];
+        const EXPECTED: [u8; 7] = [0xc0, 0x01, 0x02, 0x03, 0xad, 0xad, 0xc0];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip: Encoder = Encoder::with_crc16();
+        let mut totals = slip.encode(&INPUT, &mut output).unwrap();
+        assert_eq!(1 + INPUT.len(), totals.written);
+        totals += slip.finish(&mut output[totals.written..]).unwrap();
+        assert_eq!(&EXPECTED, &output[..totals.written]);
+    }
+
+    use crate::test_support::TestFraming;
+
+    #[test]
+    fn encode_with_custom_framing_pads_to_boundary() {
+        // START(2) + payload(2) + END(1) = 5 bytes, padded to 8.
+        const INPUT: [u8; 2] = [0x01, 0x02];
+        const EXPECTED: [u8; 8] = [0xaa, 0xbb, 0x01, 0x02, 0xcc, 0, 0, 0];
+        let mut output: [u8; 32] = [0; 32];
+
+        let mut slip = Encoder::<TestFraming>::new_with();
+        let mut totals = slip.encode(&INPUT, &mut output).unwrap();
+        totals += slip.finish(&mut output[totals.written..]).unwrap();
+        assert_eq!(&EXPECTED, &output[..totals.written]);
+    }
+
     #[test]
     fn multi_part_encode() {
         const INPUT_1: [u8; 4] = [0x01, 0x02, 0x03, ESC];
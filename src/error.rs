@@ -19,15 +19,46 @@ pub enum Error {
     BadHeaderDecode,
     /// The decoder cannot process the SLIP escape sequence.
     BadEscapeSequenceDecode,
+    /// A byte part-way through matching `Framing::END` didn't continue the
+    /// sequence. Only reachable with a multi-byte `Framing::END`, since a
+    /// single end byte can't mismatch once matched.
+    BadEndSequence,
+    /// A decoded frame exceeded the configured maximum frame size.
+    OversizedPacket,
+    /// The CRC16 trailing a decoded frame did not match the recomputed CRC
+    /// of its payload. Only possible when the decoder was constructed via
+    /// `Decoder::with_crc16`.
+    BadCrc {
+        /// The CRC16 carried in the frame.
+        expected: u16,
+        /// The CRC16 recomputed from the decoded payload.
+        actual: u16,
+    },
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(match self {
-            Error::NoOutputSpaceForHeader => "insufficient space in output buffer for header",
-            Error::NoOutputSpaceForEndByte => "insufficient space in output buffer for end byte",
-            Error::BadHeaderDecode => "malformed header",
-            Error::BadEscapeSequenceDecode => "malformed escape sequence",
-        })
+        match self {
+            Error::NoOutputSpaceForHeader => {
+                f.write_str("insufficient space in output buffer for header")
+            }
+            Error::NoOutputSpaceForEndByte => {
+                f.write_str("insufficient space in output buffer for end byte")
+            }
+            Error::BadHeaderDecode => f.write_str("malformed header"),
+            Error::BadEscapeSequenceDecode => f.write_str("malformed escape sequence"),
+            Error::BadEndSequence => f.write_str("malformed end-of-frame sequence"),
+            Error::OversizedPacket => {
+                f.write_str("decoded frame exceeded the configured maximum size")
+            }
+            Error::BadCrc { expected, actual } => write!(
+                f,
+                "CRC16 mismatch: expected {:#06x}, got {:#06x}",
+                expected, actual
+            ),
+        }
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,89 @@
+use super::*;
+
+/// The byte-level framing rules consumed by [`Encoder::new_with`] and
+/// [`Decoder::new_with`].
+///
+/// RFC 1055 SLIP is one instance of a family of escape-based framing
+/// schemes: a delimiter sequence brackets each frame, and any payload byte
+/// that collides with the escape byte or a delimiter byte is transposed to a
+/// two-byte escape sequence. Other protocols reuse the same idea with
+/// different bytes, multi-byte delimiters, or a different start/end pair
+/// (for example SML transport framing, whose `1b1b1b1b 01010101` start
+/// sequence differs from its end sequence and pads each frame to a 4-byte
+/// boundary). Implementing `Framing` drives `Encoder`/`Decoder` with those
+/// rules instead of RFC 1055's, through the same state machine.
+///
+/// [`Rfc1055`] is the zero-cost default used by `Encoder::new`/`Decoder::new`.
+///
+/// ```ignore
+/// struct Sml;
+///
+/// impl Framing for Sml {
+///     const ESC: u8 = 0x1b;
+///     const START: &'static [u8] = &[0x1b, 0x1b, 0x1b, 0x1b, 0x01, 0x01, 0x01, 0x01];
+///     const END: &'static [u8] = &[0x1b, 0x1b, 0x1b, 0x1b, 0x1a];
+///     const PAD_TO: usize = 4;
+///
+///     fn escape(byte: u8) -> Option<u8> { /* ... */ }
+///     fn unescape(byte: u8) -> Option<u8> { /* ... */ }
+/// }
+/// ```
+pub trait Framing {
+    /// Byte that introduces an escape sequence.
+    const ESC: u8;
+
+    /// Sequence written by `Encoder::encode` before a frame's payload and
+    /// matched by `Decoder::decode` before it produces output for a frame.
+    /// When equal to `END`, the decoder matches it only before the first
+    /// frame.
+    const START: &'static [u8];
+
+    /// Sequence written by `Encoder::finish` and matched by `Decoder::decode`
+    /// to end a frame. For protocols where the end of one frame doubles as
+    /// the start of the next (RFC 1055's single `END` byte), set this equal
+    /// to `START`; the decoder then expects a fresh `START` only for the
+    /// very first frame.
+    const END: &'static [u8];
+
+    /// Boundary a complete, on-the-wire frame (`START`, escaped payload, and
+    /// `END`) is padded to with trailing zero bytes, so that its length is a
+    /// multiple of this value.
+    /// `0` disables padding.
+    const PAD_TO: usize = 0;
+
+    /// Map a raw payload byte to the single byte that follows `ESC` when
+    /// escaping it, or `None` if `byte` doesn't need escaping.
+    fn escape(byte: u8) -> Option<u8>;
+
+    /// The inverse of [`Framing::escape`]: map a byte following `ESC` back
+    /// to the raw payload byte it stands for, or `None` if `byte` doesn't
+    /// complete a valid escape sequence.
+    fn unescape(byte: u8) -> Option<u8>;
+}
+
+/// Zero-cost [`Framing`] implementing classic RFC 1055 SLIP: a single `END`
+/// byte delimits frames, and `ESC`/`ESC_END`/`ESC_ESC` transpose it and
+/// `ESC` itself out of the payload.
+pub struct Rfc1055;
+
+impl Framing for Rfc1055 {
+    const ESC: u8 = ESC;
+    const START: &'static [u8] = &[END];
+    const END: &'static [u8] = &[END];
+
+    fn escape(byte: u8) -> Option<u8> {
+        match byte {
+            END => Some(ESC_END),
+            ESC => Some(ESC_ESC),
+            _ => None,
+        }
+    }
+
+    fn unescape(byte: u8) -> Option<u8> {
+        match byte {
+            ESC_END => Some(END),
+            ESC_ESC => Some(ESC),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Blocking `std::io` adapters that drive [`Encoder`]/[`Decoder`] over a
+//! [`Read`]/[`Write`], so callers don't have to manage intermediate slices
+//! themselves.
+
+use std::io::{self, Read, Write};
+use std::vec::Vec;
+
+use crate::{Decoder, Encoder};
+
+/// Size of the scratch buffer used to stage encoded/decoded bytes between
+/// the wrapped reader/writer and the underlying codec.
+const SCRATCH_LEN: usize = 256;
+
+/// Wraps a [`Write`], SLIP-encoding everything written to it between flushes
+/// as one packet.
+///
+/// The leading `END` byte is written transparently before the first byte of
+/// payload. The trailing `END` is written by [`flush`](Write::flush) or
+/// [`finish`](SlipWriter::finish), at which point the writer is ready to
+/// encode the next packet.
+pub struct SlipWriter<W> {
+    inner: W,
+    encoder: Encoder,
+}
+
+impl<W: Write> SlipWriter<W> {
+    /// Wrap `inner`, ready to SLIP-encode everything written through it.
+    pub fn new(inner: W) -> Self {
+        SlipWriter {
+            inner,
+            encoder: Encoder::new(),
+        }
+    }
+
+    /// Write the trailing `END` byte and return the wrapped writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for SlipWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut scratch = [0; SCRATCH_LEN];
+        let mut read = 0;
+        while read < buf.len() {
+            let totals = self
+                .encoder
+                .encode(&buf[read..], &mut scratch)
+                .map_err(io::Error::other)?;
+            self.inner.write_all(&scratch[..totals.written])?;
+            read += totals.read;
+        }
+        Ok(read)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut scratch = [0; SCRATCH_LEN];
+        let encoder = core::mem::replace(&mut self.encoder, Encoder::continuation());
+        let totals = encoder
+            .finish(&mut scratch)
+            .map_err(io::Error::other)?;
+        self.inner.write_all(&scratch[..totals.written])?;
+        self.inner.flush()
+    }
+}
+
+/// Wraps a [`Read`], driving a [`Decoder`] across however many reads a
+/// packet spans and yielding one fully-decoded frame per call.
+pub struct SlipReader<R> {
+    inner: R,
+    decoder: Decoder,
+    raw: [u8; SCRATCH_LEN],
+    raw_len: usize,
+    raw_pos: usize,
+}
+
+impl<R: Read> SlipReader<R> {
+    /// Wrap `inner`, ready to decode SLIP packets read through it.
+    pub fn new(inner: R) -> Self {
+        SlipReader {
+            inner,
+            decoder: Decoder::new(),
+            raw: [0; SCRATCH_LEN],
+            raw_len: 0,
+            raw_pos: 0,
+        }
+    }
+
+    /// Read and decode the next complete packet, blocking on the inner
+    /// reader until one arrives.
+    ///
+    /// Returns `Ok(None)` if `inner` reaches EOF before a frame completes.
+    pub fn read_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut packet = Vec::new();
+        let mut scratch = [0u8; SCRATCH_LEN];
+
+        loop {
+            if self.raw_pos == self.raw_len {
+                self.raw_len = self.inner.read(&mut self.raw)?;
+                self.raw_pos = 0;
+                if self.raw_len == 0 {
+                    return Ok(None);
+                }
+            }
+
+            let (consumed, decoded, end_of_packet) = self
+                .decoder
+                .decode(&self.raw[self.raw_pos..self.raw_len], &mut scratch)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            packet.extend_from_slice(decoded);
+            self.raw_pos += consumed;
+
+            if end_of_packet {
+                return Ok(Some(packet));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn write_then_read_round_trip() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = SlipWriter::new(&mut buf);
+            writer.write_all(&[0x01, 0x02, 0xc0, 0x03]).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let mut reader = SlipReader::new(Cursor::new(buf));
+        let frame = reader.read_frame().unwrap().unwrap();
+        assert_eq!(&[0x01, 0x02, 0xc0, 0x03], frame.as_slice());
+    }
+
+    #[test]
+    fn reader_returns_none_at_eof_without_a_frame() {
+        let mut reader = SlipReader::new(Cursor::new(Vec::new()));
+        assert!(reader.read_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn writer_can_encode_multiple_packets() {
+        let mut buf = Vec::new();
+        {
+            let mut writer = SlipWriter::new(&mut buf);
+            writer.write_all(&[0x01]).unwrap();
+            writer.flush().unwrap();
+            writer.write_all(&[0x02]).unwrap();
+            writer.finish().unwrap();
+        }
+
+        let mut reader = SlipReader::new(Cursor::new(buf));
+        assert_eq!(&[0x01], reader.read_frame().unwrap().unwrap().as_slice());
+        assert_eq!(&[0x02], reader.read_frame().unwrap().unwrap().as_slice());
+    }
+}
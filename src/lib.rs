@@ -76,13 +76,31 @@
 #![deny(warnings)]
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 mod decoder;
+#[cfg(feature = "alloc")]
+mod deframer;
 mod encoder;
 mod error;
+mod framing;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(test)]
+mod test_support;
 
 pub use decoder::Decoder;
+#[cfg(feature = "alloc")]
+pub use deframer::Deframer;
 pub use encoder::{EncodeTotals, Encoder};
 pub use error::{Error, Result};
+pub use framing::{Framing, Rfc1055};
+#[cfg(feature = "std")]
+pub use io::{SlipReader, SlipWriter};
 
 /// Frame end
 const END: u8 = 0xC0;
@@ -95,3 +113,20 @@ const ESC_END: u8 = 0xDC;
 
 /// Transposed frame escape
 const ESC_ESC: u8 = 0xDD;
+
+/// Initial value for the CRC16/CCITT-FALSE checksum used by
+/// `Encoder::with_crc16`/`Decoder::with_crc16`.
+const CRC16_INIT: u16 = 0xFFFF;
+
+/// Fold `byte` into a running CRC16/CCITT-FALSE checksum.
+fn crc16_update(mut crc: u16, byte: u8) -> u16 {
+    crc ^= (byte as u16) << 8;
+    for _ in 0..8 {
+        crc = if crc & 0x8000 != 0 {
+            (crc << 1) ^ 0x1021
+        } else {
+            crc << 1
+        };
+    }
+    crc
+}
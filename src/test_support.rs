@@ -0,0 +1,52 @@
+//! Test-only `Framing` fixtures shared across module test suites.
+
+use crate::Framing;
+
+/// A custom `Framing` with distinct multi-byte start/end sequences and a
+/// 4-byte padding rule, loosely modeled on SML transport framing.
+pub(crate) struct TestFraming;
+
+impl Framing for TestFraming {
+    const ESC: u8 = 0xee;
+    const START: &'static [u8] = &[0xaa, 0xbb];
+    const END: &'static [u8] = &[0xcc];
+    const PAD_TO: usize = 4;
+
+    fn escape(byte: u8) -> Option<u8> {
+        match byte {
+            0xaa => Some(0x01),
+            0xbb => Some(0x02),
+            0xcc => Some(0x03),
+            0xee => Some(0x04),
+            _ => None,
+        }
+    }
+
+    fn unescape(byte: u8) -> Option<u8> {
+        match byte {
+            0x01 => Some(0xaa),
+            0x02 => Some(0xbb),
+            0x03 => Some(0xcc),
+            0x04 => Some(0xee),
+            _ => None,
+        }
+    }
+}
+
+/// A `Framing` with distinct `START`/`END` and no padding, isolating that
+/// distinction from `TestFraming`'s `PAD_TO` behavior.
+pub(crate) struct AsymmetricFraming;
+
+impl Framing for AsymmetricFraming {
+    const ESC: u8 = 0xee;
+    const START: &'static [u8] = &[0xaa, 0xbb];
+    const END: &'static [u8] = &[0xcc];
+
+    fn escape(byte: u8) -> Option<u8> {
+        TestFraming::escape(byte)
+    }
+
+    fn unescape(byte: u8) -> Option<u8> {
+        TestFraming::unescape(byte)
+    }
+}